@@ -1,10 +1,12 @@
 pub use self::checkstyle::*;
 pub use self::diff::*;
 pub use self::files::*;
+pub use self::files_with_diff::*;
 pub use self::json::*;
 pub use self::modified_lines::*;
 pub use self::stdout::*;
 
+use std::borrow::Cow;
 use std::fs;
 use std::io::{self, Write};
 use std::path::Path;
@@ -18,6 +20,7 @@ use crate::{config::FileName, FormatReport, FormatResult, NewlineStyle};
 pub mod checkstyle;
 pub mod diff;
 pub mod files;
+pub mod files_with_diff;
 pub mod json;
 pub mod modified_lines;
 pub mod rustfmt_diff;
@@ -27,6 +30,13 @@ pub struct FormattedFile<'a> {
     pub filename: &'a FileName,
     pub original_text: &'a str,
     pub formatted_text: &'a str,
+    /// `true` when `original_text` and `formatted_text` are identical
+    /// line-by-line but differ in their line endings (e.g. CRLF vs LF).
+    /// Emitters should surface this even when they otherwise have nothing
+    /// to report, since a plain line-based diff is empty in this case.
+    /// `write_file` computes it before applying the `EndOfLine` policy, so a
+    /// final newline that the policy alone removes does not set it.
+    pub newline_conflict: bool,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -80,6 +90,9 @@ pub enum EmitMode {
     /// This option is designed to be run in CI where a non-zero exit signifies
     /// non-standard code formatting. Used for `--check`.
     Diff,
+    /// Prints the path of each file whose formatted output differs from its
+    /// original contents, one per line, without printing the diff itself.
+    FilesWithDiff,
 }
 
 /// Client-preference for coloured output.
@@ -94,11 +107,21 @@ pub enum Color {
 }
 
 impl Color {
-    /// Whether we should use a coloured terminal.
-    pub fn use_colored_tty(self) -> bool {
+    /// Whether an emitter should colorize the output it writes.
+    /// `Always`/`Never` are unconditional; `Auto` colorizes only when
+    /// `output_is_terminal` says the destination is a real terminal.
+    ///
+    /// An emitter's `output: &mut dyn Write` may be a real terminal, a
+    /// `Vec<u8>`, a file, or any other sink a caller passes in, and there is
+    /// no way to recover "is this a tty" from a type-erased `Write` alone --
+    /// so the caller that actually knows what `out` is (e.g. the binary
+    /// wiring up `emit_format_report`) must supply `output_is_terminal`
+    /// itself, typically via `EmitterConfig::output_is_terminal`.
+    pub fn should_colorize(self, output_is_terminal: bool) -> bool {
         match self {
-            Color::Always | Color::Auto => true,
+            Color::Always => true,
             Color::Never => false,
+            Color::Auto => output_is_terminal,
         }
     }
 }
@@ -120,6 +143,21 @@ impl Default for Verbosity {
     }
 }
 
+/// Controls what `write_file` does to the terminal newline of
+/// `formatted_text` before it is handed to an emitter, independent of the
+/// `newline_style` (`\n` vs `\r\n`) used within the file.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum EndOfLine {
+    /// Guarantee exactly one trailing newline.
+    #[default]
+    Ensure,
+    /// Match whatever `original_text` ended with, including no trailing
+    /// newline at all.
+    Preserve,
+    /// Remove all trailing blank lines, leaving no final newline.
+    Strip,
+}
+
 impl std::str::FromStr for EmitMode {
     type Err = String;
 
@@ -129,6 +167,7 @@ impl std::str::FromStr for EmitMode {
             "stdout" => Ok(EmitMode::Stdout),
             "checkstyle" => Ok(EmitMode::Checkstyle),
             "json" => Ok(EmitMode::Json),
+            "files-with-diff" => Ok(EmitMode::FilesWithDiff),
             _ => Err(format!("unknown emit mode `{}`", s)),
         }
     }
@@ -140,6 +179,21 @@ pub struct EmitterConfig {
     pub color: Color,
     pub verbosity: Verbosity,
     pub print_filename: bool,
+    /// Whether the destination the emitter is about to write to is an
+    /// actual terminal. `Color::Auto` colorizes only when this is `true`.
+    /// The library has no way to answer this itself -- an emitter's
+    /// `output: &mut dyn Write` may be a real terminal, a `Vec<u8>`, a file,
+    /// or any other sink -- so the caller that owns the concrete output
+    /// (e.g. the binary, via `std::io::stdout().is_terminal()` when `out`
+    /// genuinely is stdout) must set this explicitly.
+    pub output_is_terminal: bool,
+    /// When `true` and `EmitMode::Files` is in effect, `FilesEmitter` writes
+    /// the pre-format contents to a `<file>.bk` backup before overwriting
+    /// the original, mirroring the internal `make_backup` option.
+    pub backup: bool,
+    /// The final-newline convention `write_file` applies to `formatted_text`
+    /// before it reaches an emitter.
+    pub end_of_line: EndOfLine,
 }
 
 impl Default for EmitterConfig {
@@ -149,6 +203,9 @@ impl Default for EmitterConfig {
             color: Color::Auto,
             verbosity: Verbosity::Normal,
             print_filename: false,
+            output_is_terminal: false,
+            backup: false,
+            end_of_line: EndOfLine::Ensure,
         }
     }
 }
@@ -166,7 +223,9 @@ where
 
     emitter.emit_header(out)?;
     for (filename, format_result) in format_report.format_result_as_rc().borrow().iter() {
-        has_diff |= write_file(None, filename, &format_result, out, &mut *emitter)?.has_diff;
+        has_diff |=
+            write_file(None, filename, format_result, out, &mut *emitter, config.end_of_line)?
+                .has_diff;
     }
     emitter.emit_footer(out)?;
 
@@ -179,6 +238,7 @@ pub(crate) fn write_file<T>(
     formatted_result: &FormatResult,
     out: &mut T,
     emitter: &mut dyn Emitter,
+    end_of_line: EndOfLine,
 ) -> Result<EmitterResult, EmitterError>
 where
     T: Write,
@@ -212,22 +272,197 @@ where
             }
         };
 
+    // Compute the newline-style conflict against the pre-`EndOfLine` text:
+    // `apply_end_of_line` below may itself add or strip a trailing newline
+    // per policy, and that intentional change must not be mistaken for the
+    // CRLF-vs-LF conflict this flag exists to report. Nor is a conflict
+    // reported when the policy leaves the text identical to the original.
+    let pre_eol_text = formatted_result.formatted_text();
+    let formatted_text = apply_end_of_line(pre_eol_text, original_text.as_str(), end_of_line);
+
+    let newline_conflict = original_text.as_str() != pre_eol_text
+        && original_text.as_str() != formatted_text.as_ref()
+        && lines_match_ignoring_newlines(original_text.as_str(), pre_eol_text);
+
     let formatted_file = FormattedFile {
         filename,
         original_text: original_text.as_str(),
-        formatted_text: formatted_result.formatted_text(),
+        formatted_text: formatted_text.as_ref(),
+        newline_conflict,
     };
 
     emitter.emit_formatted_file(out, formatted_file)
 }
 
+/// Compares `a` and `b` line-by-line, ignoring the particular line ending
+/// each line uses. This lets callers tell apart a genuine content diff from
+/// a file that is byte-for-byte different only because of its newline style.
+fn lines_match_ignoring_newlines(a: &str, b: &str) -> bool {
+    a.lines().eq(b.lines())
+}
+
+/// The line terminator in use within `text`: `"\r\n"` if any CRLF line
+/// ending is present, `"\n"` otherwise. `formatted_text` has already had the
+/// configured `newline_style` applied to it by the time it reaches here, so
+/// sniffing its own terminator is more reliable than trusting a separately
+/// threaded-through `NewlineStyle::Auto`/`Native`, which don't name a
+/// concrete line ending on their own.
+fn line_terminator(text: &str) -> &'static str {
+    if text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Strips every trailing occurrence of `eol` from `text`.
+fn strip_trailing_terminators<'a>(text: &'a str, eol: &str) -> &'a str {
+    let mut stripped = text;
+    while let Some(rest) = stripped.strip_suffix(eol) {
+        stripped = rest;
+    }
+    stripped
+}
+
+/// Applies the `EndOfLine` policy to `formatted_text`, using `original_text`
+/// as the reference for `EndOfLine::Preserve`. Operates on whole `"\r\n"` or
+/// `"\n"` terminators (matching `formatted_text`'s own `newline_style`)
+/// rather than raw `'\n'` bytes, so CRLF files are handled correctly instead
+/// of leaving a dangling `'\r'` or failing to collapse multiple blank lines.
+fn apply_end_of_line<'a>(
+    formatted_text: &'a str,
+    original_text: &str,
+    end_of_line: EndOfLine,
+) -> Cow<'a, str> {
+    let eol = line_terminator(formatted_text);
+    let stripped = strip_trailing_terminators(formatted_text, eol);
+
+    match end_of_line {
+        EndOfLine::Ensure => {
+            if stripped.len() + eol.len() == formatted_text.len() {
+                Cow::Borrowed(formatted_text)
+            } else {
+                Cow::Owned(format!("{}{}", stripped, eol))
+            }
+        }
+        EndOfLine::Strip => Cow::Borrowed(stripped),
+        EndOfLine::Preserve => {
+            if original_text.ends_with('\n') {
+                apply_end_of_line(formatted_text, original_text, EndOfLine::Ensure)
+            } else {
+                Cow::Borrowed(stripped)
+            }
+        }
+    }
+}
+
 fn create_emitter(emitter_config: EmitterConfig) -> Box<dyn Emitter> {
+    // Every emitter is constructed from the full `EmitterConfig` so that
+    // `--color` and `--verbose`/`--quiet` behave consistently no matter
+    // which `--emit` value is in effect, instead of only the emitters that
+    // happened to need `color` at the time they were written.
     match emitter_config.emit_mode {
         EmitMode::Files => Box::new(FilesEmitter::new(emitter_config)),
         EmitMode::Stdout => Box::new(StdoutEmitter::new(emitter_config)),
-        EmitMode::Json => Box::new(JsonEmitter::default()),
-        EmitMode::ModifiedLines => Box::new(ModifiedLinesEmitter::default()),
-        EmitMode::Checkstyle => Box::new(CheckstyleEmitter::default()),
+        EmitMode::Json => Box::new(JsonEmitter::new(emitter_config)),
+        EmitMode::ModifiedLines => Box::new(ModifiedLinesEmitter::new(emitter_config)),
+        EmitMode::Checkstyle => Box::new(CheckstyleEmitter::new(emitter_config)),
         EmitMode::Diff => Box::new(DiffEmitter::new(emitter_config)),
+        EmitMode::FilesWithDiff => Box::new(FilesWithDiffEmitter::new(emitter_config)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_adds_missing_trailing_newline() {
+        assert_eq!(apply_end_of_line("a\nb", "x", EndOfLine::Ensure), "a\nb\n");
+    }
+
+    #[test]
+    fn ensure_collapses_multiple_trailing_newlines() {
+        assert_eq!(apply_end_of_line("a\nb\n\n\n", "x", EndOfLine::Ensure), "a\nb\n");
+    }
+
+    #[test]
+    fn ensure_is_a_no_op_with_exactly_one_trailing_newline() {
+        assert_eq!(apply_end_of_line("a\nb\n", "x", EndOfLine::Ensure), "a\nb\n");
+    }
+
+    #[test]
+    fn strip_removes_every_trailing_newline() {
+        assert_eq!(apply_end_of_line("a\nb\n\n\n", "x", EndOfLine::Strip), "a\nb");
+        assert_eq!(apply_end_of_line("a\nb", "x", EndOfLine::Strip), "a\nb");
+    }
+
+    #[test]
+    fn preserve_matches_an_original_with_no_trailing_newline() {
+        assert_eq!(apply_end_of_line("a\nb\n", "x", EndOfLine::Preserve), "a\nb");
+    }
+
+    #[test]
+    fn preserve_matches_an_original_with_one_trailing_newline() {
+        assert_eq!(apply_end_of_line("a\nb", "x\n", EndOfLine::Preserve), "a\nb\n");
+    }
+
+    #[test]
+    fn ensure_collapses_multiple_trailing_crlf_newlines() {
+        assert_eq!(
+            apply_end_of_line("a\r\nb\r\n\r\n\r\n", "x", EndOfLine::Ensure),
+            "a\r\nb\r\n",
+        );
+    }
+
+    #[test]
+    fn ensure_is_a_no_op_with_exactly_one_trailing_crlf_newline() {
+        assert_eq!(
+            apply_end_of_line("a\r\nb\r\n", "x", EndOfLine::Ensure),
+            "a\r\nb\r\n",
+        );
+    }
+
+    #[test]
+    fn strip_removes_every_trailing_crlf_newline_without_a_dangling_cr() {
+        assert_eq!(
+            apply_end_of_line("a\r\nb\r\n\r\n\r\n", "x", EndOfLine::Strip),
+            "a\r\nb",
+        );
+    }
+
+    #[test]
+    fn preserve_matches_a_crlf_original_with_no_trailing_newline() {
+        assert_eq!(
+            apply_end_of_line("a\r\nb\r\n", "x", EndOfLine::Preserve),
+            "a\r\nb",
+        );
+    }
+
+    #[test]
+    fn preserve_matches_a_crlf_original_with_one_trailing_newline() {
+        assert_eq!(
+            apply_end_of_line("a\r\nb", "x\r\n", EndOfLine::Preserve),
+            "a\r\nb\r\n",
+        );
+    }
+
+    #[test]
+    fn pure_end_of_line_change_is_not_a_newline_conflict() {
+        // `original` and the formatted text agree exactly before `EndOfLine`
+        // is applied -- no CRLF/LF conflict here at all.
+        let original = "a\nb\n";
+        let pre_eol_text = "a\nb\n";
+        let newline_conflict =
+            original != pre_eol_text && lines_match_ignoring_newlines(original, pre_eol_text);
+        assert!(!newline_conflict);
+
+        // `Strip` then removes the trailing newline relative to `original`
+        // as an intentional policy choice. If `newline_conflict` were
+        // (incorrectly) computed from this adjusted text instead of
+        // `pre_eol_text`, it would wrongly come out `true` here.
+        let adjusted = apply_end_of_line(pre_eol_text, original, EndOfLine::Strip);
+        assert_eq!(adjusted.as_ref(), "a\nb");
+        assert_ne!(adjusted.as_ref(), original);
     }
 }
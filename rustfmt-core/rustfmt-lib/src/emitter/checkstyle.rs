@@ -0,0 +1,155 @@
+use std::io::{self, Write};
+
+use crate::emitter::rustfmt_diff::{make_diff, DiffLine};
+use crate::emitter::{Emitter, EmitterConfig, EmitterError, EmitterResult, FormattedFile, Verbosity};
+
+const CONTEXT_SIZE: usize = 0;
+
+/// Emits a Checkstyle-compatible XML report, one `<file>` element per
+/// file that needs reformatting; unchanged files get no `<file>` element.
+#[derive(Debug, Default)]
+pub struct CheckstyleEmitter {
+    verbosity: Verbosity,
+}
+
+impl CheckstyleEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        CheckstyleEmitter {
+            verbosity: config.verbosity,
+        }
+    }
+}
+
+impl Emitter for CheckstyleEmitter {
+    fn emit_header(&self, output: &mut dyn Write) -> Result<(), EmitterError> {
+        writeln!(output, "<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+        writeln!(output, "<checkstyle version=\"4.3\">")?;
+        Ok(())
+    }
+
+    fn emit_footer(&self, output: &mut dyn Write) -> Result<(), EmitterError> {
+        writeln!(output, "</checkstyle>")?;
+        Ok(())
+    }
+
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+            newline_conflict,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        let diff = make_diff(original_text, formatted_text, CONTEXT_SIZE);
+        let has_diff = !diff.is_empty() || newline_conflict;
+
+        if has_diff {
+            write!(output, "<file name=\"{}\">", filename)?;
+            write_checkstyle_errors(&mut *output, diff)?;
+            if newline_conflict {
+                write!(
+                    output,
+                    "<error severity=\"warning\" message=\"Newline style conflict: file differs from the formatted output only in its line endings\" source=\"rustfmt\"/>",
+                )?;
+            }
+            write!(output, "</file>")?;
+        } else if self.verbosity == Verbosity::Verbose {
+            write!(output, "<!-- {}: no changes -->", filename)?;
+        }
+
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+fn write_checkstyle_errors<T: Write>(
+    mut writer: T,
+    diff: Vec<crate::emitter::rustfmt_diff::Mismatch>,
+) -> Result<(), io::Error> {
+    for mismatch in diff {
+        for line in mismatch.lines {
+            if let DiffLine::Expected(ref str) = line {
+                write!(
+                    writer,
+                    "<error line=\"{}\" severity=\"warning\" message=\"Formatting issue: {}\" source=\"rustfmt\"/>",
+                    mismatch.line_number, xml_escape_str(str),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn xml_escape_str(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileName;
+
+    fn emit(verbosity: Verbosity, original: &str, formatted: &str) -> String {
+        let filename = FileName::Real("src/lib.rs".into());
+        let config = EmitterConfig {
+            verbosity,
+            ..EmitterConfig::default()
+        };
+        let mut emitter = CheckstyleEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn verbose_notes_unchanged_files() {
+        let output = emit(Verbosity::Verbose, "fn main() {}\n", "fn main() {}\n");
+        assert_eq!(output, "<!-- src/lib.rs: no changes -->");
+    }
+
+    #[test]
+    fn normal_is_silent_about_unchanged_files() {
+        let output = emit(Verbosity::Normal, "fn main() {}\n", "fn main() {}\n");
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn a_pure_newline_conflict_emits_a_warning_error() {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = CheckstyleEmitter::new(EmitterConfig::default());
+        let mut out = Vec::new();
+        let result = emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: "fn main() {}\n",
+                    formatted_text: "fn main() {}\n",
+                    newline_conflict: true,
+                },
+            )
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(result.has_diff);
+        assert!(
+            output.contains("Newline style conflict"),
+            "expected a newline-conflict warning: {}",
+            output
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::io::Write;
+
+use crate::emitter::rustfmt_diff::{make_diff, DiffLine};
+use crate::emitter::{
+    Emitter, EmitterConfig, EmitterError, EmitterResult, FormattedFile, Verbosity,
+};
+
+const DIFF_CONTEXT_SIZE: usize = 3;
+
+/// Prints a unified-style diff between the original and formatted source,
+/// used for `--check` and `--emit diff`.
+pub struct DiffEmitter {
+    config: EmitterConfig,
+    use_color: bool,
+}
+
+impl DiffEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        let use_color = config.color.should_colorize(config.output_is_terminal);
+        DiffEmitter { config, use_color }
+    }
+
+    fn write_line(
+        &self,
+        output: &mut dyn Write,
+        prefix: char,
+        color_code: &str,
+        text: &str,
+    ) -> Result<(), EmitterError> {
+        if self.use_color {
+            writeln!(output, "\u{1b}[{}m{}{}\u{1b}[0m", color_code, prefix, text)?;
+        } else {
+            writeln!(output, "{}{}", prefix, text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Emitter for DiffEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+            newline_conflict,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        let mismatches = make_diff(original_text, formatted_text, DIFF_CONTEXT_SIZE);
+        let has_diff = !mismatches.is_empty() || newline_conflict;
+
+        if mismatches.is_empty() {
+            if newline_conflict {
+                writeln!(output, "Incorrect newline style in {}", filename)?;
+            }
+            return Ok(EmitterResult { has_diff });
+        }
+
+        if self.config.verbosity == Verbosity::Verbose {
+            writeln!(output, "Diff in {}:", filename)?;
+        }
+
+        for mismatch in mismatches {
+            writeln!(output, "Diff in {} at line {}:", filename, mismatch.line_number_orig)?;
+            for line in mismatch.lines {
+                match line {
+                    DiffLine::Context(ref str) => writeln!(output, " {}", str)?,
+                    DiffLine::Expected(ref str) => self.write_line(output, '+', "32", str)?,
+                    DiffLine::Resulting(ref str) => self.write_line(output, '-', "31", str)?,
+                }
+            }
+        }
+
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileName;
+    use crate::emitter::Color;
+
+    fn emit(config: EmitterConfig, original: &str, formatted: &str) -> String {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = DiffEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn verbose_adds_an_overall_banner_before_the_per_hunk_headers() {
+        let config = EmitterConfig {
+            verbosity: Verbosity::Verbose,
+            ..EmitterConfig::default()
+        };
+        let output = emit(config, "a\n", "b\n");
+        assert!(output.starts_with("Diff in src/lib.rs:\n"));
+    }
+
+    #[test]
+    fn normal_has_no_overall_banner() {
+        let output = emit(EmitterConfig::default(), "a\n", "b\n");
+        assert!(!output.contains("Diff in src/lib.rs:\n"));
+        assert!(output.starts_with("Diff in src/lib.rs at line"));
+    }
+
+    #[test]
+    fn auto_color_does_not_colorize_when_the_output_is_not_a_terminal() {
+        let config = EmitterConfig {
+            color: Color::Auto,
+            output_is_terminal: false,
+            ..EmitterConfig::default()
+        };
+        let output = emit(config, "a\n", "b\n");
+        assert!(!output.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn auto_color_colorizes_when_the_output_is_a_terminal() {
+        let config = EmitterConfig {
+            color: Color::Auto,
+            output_is_terminal: true,
+            ..EmitterConfig::default()
+        };
+        let output = emit(config, "a\n", "b\n");
+        assert!(output.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn a_pure_newline_conflict_is_reported_with_no_hunks() {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = DiffEmitter::new(EmitterConfig::default());
+        let mut out = Vec::new();
+        let result = emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: "a\n",
+                    formatted_text: "a\n",
+                    newline_conflict: true,
+                },
+            )
+            .unwrap();
+        assert!(result.has_diff);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Incorrect newline style in src/lib.rs\n",
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::config::FileName;
+use crate::emitter::{Emitter, EmitterConfig, EmitterError, EmitterResult, FormattedFile, Verbosity};
+
+/// Writes the formatted output back to disk, overwriting the original file.
+pub struct FilesEmitter {
+    print_filename: bool,
+    verbosity: Verbosity,
+    backup: bool,
+}
+
+impl FilesEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        FilesEmitter {
+            print_filename: config.print_filename,
+            verbosity: config.verbosity,
+            backup: config.backup,
+        }
+    }
+}
+
+fn backup_path(filepath: &std::path::Path) -> PathBuf {
+    let mut path: OsString = filepath.as_os_str().to_owned();
+    path.push(".bk");
+    PathBuf::from(path)
+}
+
+impl Emitter for FilesEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+            newline_conflict,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        let has_diff = original_text != formatted_text || newline_conflict;
+
+        let filepath = match filename {
+            FileName::Real(path) => path,
+            _ => return Err(EmitterError::InvalidInputForFiles),
+        };
+
+        // Rewrite the file whenever its content or its line endings would
+        // differ from `formatted_text`, so a newline-only conflict gets
+        // corrected just like a content mismatch would.
+        if has_diff {
+            if self.backup && original_text != formatted_text {
+                fs::write(backup_path(filepath), original_text)?;
+            }
+
+            fs::write(filepath, formatted_text)?;
+        }
+
+        let show_banner = self.verbosity != Verbosity::Quiet
+            && (self.print_filename || self.verbosity == Verbosity::Verbose);
+        if has_diff && show_banner {
+            writeln!(output, "{}", filename)?;
+        }
+
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    fn unique_path(name: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!("rustfmt-files-emitter-test-{}-{}", std::process::id(), name));
+        path
+    }
+
+    fn emit(path: &std::path::Path, original: &str, formatted: &str) {
+        let filename = FileName::Real(path.to_owned());
+        let config = EmitterConfig {
+            backup: true,
+            ..EmitterConfig::default()
+        };
+        let mut emitter = FilesEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn backup_is_created_when_content_changes() {
+        let path = unique_path("changed.rs");
+        fs::write(&path, "fn main(){}").unwrap();
+
+        emit(&path, "fn main(){}", "fn main() {}\n");
+
+        let bk = backup_path(&path);
+        let mut contents = String::new();
+        fs::File::open(&bk)
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "fn main(){}");
+
+        fs::remove_file(&path).ok();
+        fs::remove_file(&bk).ok();
+    }
+
+    #[test]
+    fn no_backup_is_created_when_nothing_changed() {
+        let path = unique_path("unchanged.rs");
+        fs::write(&path, "fn main() {}\n").unwrap();
+
+        emit(&path, "fn main() {}\n", "fn main() {}\n");
+
+        assert!(!backup_path(&path).exists());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn verbose_prints_the_banner_even_without_print_filename() {
+        let path = unique_path("verbose.rs");
+        fs::write(&path, "fn main(){}").unwrap();
+        let filename = FileName::Real(path.clone());
+        let config = EmitterConfig {
+            verbosity: Verbosity::Verbose,
+            print_filename: false,
+            ..EmitterConfig::default()
+        };
+        let mut emitter = FilesEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: "fn main(){}",
+                    formatted_text: "fn main() {}\n",
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", filename));
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn quiet_suppresses_the_banner_even_with_print_filename() {
+        let path = unique_path("quiet.rs");
+        fs::write(&path, "fn main(){}").unwrap();
+        let filename = FileName::Real(path.clone());
+        let config = EmitterConfig {
+            verbosity: Verbosity::Quiet,
+            print_filename: true,
+            ..EmitterConfig::default()
+        };
+        let mut emitter = FilesEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: "fn main(){}",
+                    formatted_text: "fn main() {}\n",
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+        assert_eq!(out, b"");
+
+        fs::remove_file(&path).ok();
+    }
+}
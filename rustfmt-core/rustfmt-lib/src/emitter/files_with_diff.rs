@@ -0,0 +1,77 @@
+use std::io::Write;
+
+use super::{Emitter, EmitterConfig, EmitterResult, FormattedFile};
+
+/// Prints the name of each file whose formatted output differs from the
+/// original, one per line. Unlike `DiffEmitter` or `JsonEmitter` it never
+/// prints the diff itself, which makes it cheap for scripts that only need
+/// to know *which* files would change under `--check`.
+pub struct FilesWithDiffEmitter;
+
+impl FilesWithDiffEmitter {
+    pub fn new(_config: EmitterConfig) -> Self {
+        FilesWithDiffEmitter
+    }
+}
+
+impl Emitter for FilesWithDiffEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+            newline_conflict,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, super::EmitterError> {
+        let has_diff = original_text != formatted_text || newline_conflict;
+
+        // The changed-file path *is* this emitter's payload, not chatter —
+        // `Verbosity::Quiet` only suppresses extras beyond that, so it must
+        // print unconditionally whenever `has_diff` is set.
+        if has_diff {
+            writeln!(output, "{}", filename)?;
+        }
+
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileName;
+
+    fn emit(original: &str, formatted: &str, newline_conflict: bool) -> (bool, String) {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = FilesWithDiffEmitter::new(EmitterConfig::default());
+        let mut out = Vec::new();
+        let result = emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict,
+                },
+            )
+            .unwrap();
+        (result.has_diff, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn a_pure_newline_conflict_still_prints_the_filename() {
+        let (has_diff, output) = emit("a\n", "a\n", true);
+        assert!(has_diff);
+        assert_eq!(output, "src/lib.rs\n");
+    }
+
+    #[test]
+    fn an_unchanged_file_prints_nothing() {
+        let (has_diff, output) = emit("a\n", "a\n", false);
+        assert!(!has_diff);
+        assert_eq!(output, "");
+    }
+}
@@ -0,0 +1,194 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::emitter::rustfmt_diff::{make_diff, DiffLine};
+use crate::emitter::{Emitter, EmitterConfig, EmitterError, EmitterResult, FormattedFile, Verbosity};
+
+const CONTEXT_SIZE: usize = 3;
+
+#[derive(Debug, Default, Serialize)]
+struct MismatchedBlock {
+    original_begin_line: u32,
+    original_end_line: u32,
+    expected_begin_line: u32,
+    expected_end_line: u32,
+    original: String,
+    expected: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct MismatchedFile {
+    name: String,
+    mismatches: Vec<MismatchedBlock>,
+    /// `true` when `name` has no content mismatches but still differs from
+    /// the formatted output in its line endings.
+    #[serde(skip_serializing_if = "is_false")]
+    newline_style_conflict: bool,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+/// Emits the mismatched files as a JSON array, one object per file that
+/// needs reformatting. Returns `[]` when nothing differs.
+#[derive(Debug, Default)]
+pub struct JsonEmitter {
+    mismatched_files: Vec<MismatchedFile>,
+    verbosity: Verbosity,
+}
+
+impl JsonEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        JsonEmitter {
+            mismatched_files: Vec::new(),
+            verbosity: config.verbosity,
+        }
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        _output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+            newline_conflict,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        let diff = make_diff(original_text, formatted_text, CONTEXT_SIZE);
+        let has_diff = !diff.is_empty() || newline_conflict;
+
+        if has_diff {
+            let mismatches = diff
+                .into_iter()
+                .map(|mismatch| {
+                    let original_begin_line = mismatch.line_number_orig;
+                    let expected_begin_line = mismatch.line_number;
+                    let mut original_end_line = original_begin_line;
+                    let mut expected_end_line = expected_begin_line;
+                    let mut original_lines = Vec::new();
+                    let mut expected_lines = Vec::new();
+
+                    for line in mismatch.lines {
+                        match line {
+                            DiffLine::Context(_) => {}
+                            DiffLine::Expected(ref str) => {
+                                expected_end_line += 1;
+                                expected_lines.push(str.to_owned());
+                            }
+                            DiffLine::Resulting(ref str) => {
+                                original_end_line += 1;
+                                original_lines.push(str.to_owned());
+                            }
+                        }
+                    }
+
+                    MismatchedBlock {
+                        original_begin_line,
+                        original_end_line,
+                        expected_begin_line,
+                        expected_end_line,
+                        original: original_lines.join("\n"),
+                        expected: expected_lines.join("\n"),
+                    }
+                })
+                .collect();
+
+            self.mismatched_files.push(MismatchedFile {
+                name: filename.to_string(),
+                mismatches,
+                newline_style_conflict: newline_conflict,
+            });
+        }
+
+        Ok(EmitterResult { has_diff })
+    }
+
+    fn emit_footer(&self, output: &mut dyn Write) -> Result<(), EmitterError> {
+        // `Verbose` pretty-prints the array so a human reading `--emit json`
+        // output directly doesn't have to pipe it through a formatter; both
+        // forms are equally valid JSON for scripts.
+        if self.verbosity == Verbosity::Verbose {
+            Ok(serde_json::to_writer_pretty(output, &self.mismatched_files)?)
+        } else {
+            Ok(serde_json::to_writer(output, &self.mismatched_files)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileName;
+
+    fn footer(verbosity: Verbosity, original: &str, formatted: &str) -> String {
+        let filename = FileName::Real("src/lib.rs".into());
+        let config = EmitterConfig {
+            verbosity,
+            ..EmitterConfig::default()
+        };
+        let mut emitter = JsonEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+        emitter.emit_footer(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn verbose_pretty_prints_the_json_array() {
+        let output = footer(Verbosity::Verbose, "a\n", "b\n");
+        assert!(output.contains('\n'), "expected pretty-printed output: {}", output);
+    }
+
+    #[test]
+    fn normal_prints_compact_json() {
+        let output = footer(Verbosity::Normal, "a\n", "b\n");
+        assert!(!output.contains('\n'), "expected compact output: {}", output);
+    }
+
+    #[test]
+    fn a_pure_newline_conflict_is_reported_with_no_mismatches() {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = JsonEmitter::new(EmitterConfig::default());
+        let mut out = Vec::new();
+        let result = emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: "a\n",
+                    formatted_text: "a\n",
+                    newline_conflict: true,
+                },
+            )
+            .unwrap();
+        assert!(result.has_diff);
+
+        emitter.emit_footer(&mut out).unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(
+            output.contains("\"newline_style_conflict\":true"),
+            "expected newline_style_conflict to be reported: {}",
+            output
+        );
+        assert!(
+            output.contains("\"mismatches\":[]"),
+            "expected no content mismatches: {}",
+            output
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::io::Write;
+
+use crate::emitter::rustfmt_diff::{make_diff, DiffLine};
+use crate::emitter::{Emitter, EmitterConfig, EmitterError, EmitterResult, FormattedFile, Verbosity};
+
+/// Emits only the line ranges that changed, in the form
+/// `<original_start> <original_end> <replacement lines...>`. Used internally
+/// by editor integrations that want to patch in-place without a full diff;
+/// not exposed as a public `--emit` value.
+#[derive(Debug, Default)]
+pub struct ModifiedLinesEmitter {
+    verbosity: Verbosity,
+}
+
+impl ModifiedLinesEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        ModifiedLinesEmitter {
+            verbosity: config.verbosity,
+        }
+    }
+}
+
+impl Emitter for ModifiedLinesEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            original_text,
+            formatted_text,
+            newline_conflict,
+            ..
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        let mismatches = make_diff(original_text, formatted_text, 0);
+        let has_diff = !mismatches.is_empty() || newline_conflict;
+
+        for mismatch in &mismatches {
+            let expected: Vec<&str> = mismatch
+                .lines
+                .iter()
+                .filter_map(|line| match line {
+                    DiffLine::Expected(ref str) => Some(str.as_str()),
+                    _ => None,
+                })
+                .collect();
+            let removed = mismatch
+                .lines
+                .iter()
+                .filter(|line| matches!(line, DiffLine::Resulting(_)))
+                .count();
+
+            writeln!(
+                output,
+                "{} {}",
+                mismatch.line_number_orig,
+                mismatch.line_number_orig + removed as u32,
+            )?;
+            for line in &expected {
+                writeln!(output, "{}", line)?;
+            }
+        }
+
+        // Consumers of this format patch in line ranges, and a newline-only
+        // conflict has none to offer, so there's nothing to print by
+        // default. `--verbose` opts in to an explicit `0 0` marker noting
+        // that the file still needs rewriting even though no lines moved.
+        if mismatches.is_empty() && newline_conflict && self.verbosity == Verbosity::Verbose {
+            writeln!(output, "0 0")?;
+        }
+
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emit(verbosity: Verbosity, original: &str, formatted: &str, newline_conflict: bool) -> String {
+        let config = EmitterConfig {
+            verbosity,
+            ..EmitterConfig::default()
+        };
+        let mut emitter = ModifiedLinesEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &crate::config::FileName::Stdin,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict,
+                },
+            )
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn verbose_adds_a_marker_for_a_pure_newline_conflict() {
+        let output = emit(Verbosity::Verbose, "a\n", "a\n", true);
+        assert_eq!(output, "0 0\n");
+    }
+
+    #[test]
+    fn normal_omits_the_marker_for_a_pure_newline_conflict() {
+        let output = emit(Verbosity::Normal, "a\n", "a\n", true);
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn a_pure_newline_conflict_is_still_reported_as_a_diff() {
+        let config = EmitterConfig::default();
+        let mut emitter = ModifiedLinesEmitter::new(config);
+        let mut out = Vec::new();
+        let result = emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &crate::config::FileName::Stdin,
+                    original_text: "a\n",
+                    formatted_text: "a\n",
+                    newline_conflict: true,
+                },
+            )
+            .unwrap();
+        assert!(result.has_diff);
+    }
+}
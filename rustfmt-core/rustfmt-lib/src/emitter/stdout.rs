@@ -0,0 +1,110 @@
+use std::io::Write;
+
+use crate::emitter::{Emitter, EmitterConfig, EmitterError, EmitterResult, FormattedFile, Verbosity};
+
+/// Writes the formatted output straight to stdout, optionally prefixed with
+/// the source filename.
+pub struct StdoutEmitter {
+    print_filename: bool,
+    verbosity: Verbosity,
+}
+
+impl StdoutEmitter {
+    pub fn new(config: EmitterConfig) -> Self {
+        StdoutEmitter {
+            print_filename: config.print_filename,
+            verbosity: config.verbosity,
+        }
+    }
+}
+
+impl Emitter for StdoutEmitter {
+    fn emit_formatted_file(
+        &mut self,
+        output: &mut dyn Write,
+        FormattedFile {
+            filename,
+            original_text,
+            formatted_text,
+            newline_conflict,
+        }: FormattedFile<'_>,
+    ) -> Result<EmitterResult, EmitterError> {
+        let has_diff = original_text != formatted_text || newline_conflict;
+
+        // `print_filename` prints the banner and `Verbose` prints it even
+        // when the caller didn't ask, but `Quiet` never does.
+        let show_banner = self.verbosity != Verbosity::Quiet
+            && (self.print_filename || self.verbosity == Verbosity::Verbose);
+        if show_banner {
+            writeln!(output, "{}:", filename)?;
+        }
+        write!(output, "{}", formatted_text)?;
+
+        Ok(EmitterResult { has_diff })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FileName;
+
+    fn emit(config: EmitterConfig, original: &str, formatted: &str) -> String {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = StdoutEmitter::new(config);
+        let mut out = Vec::new();
+        emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: original,
+                    formatted_text: formatted,
+                    newline_conflict: false,
+                },
+            )
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn verbose_prints_the_banner_even_without_print_filename() {
+        let config = EmitterConfig {
+            verbosity: Verbosity::Verbose,
+            print_filename: false,
+            ..EmitterConfig::default()
+        };
+        let output = emit(config, "a\n", "a\n");
+        assert_eq!(output, "src/lib.rs:\na\n");
+    }
+
+    #[test]
+    fn quiet_suppresses_the_banner_even_with_print_filename() {
+        let config = EmitterConfig {
+            verbosity: Verbosity::Quiet,
+            print_filename: true,
+            ..EmitterConfig::default()
+        };
+        let output = emit(config, "a\n", "a\n");
+        assert_eq!(output, "a\n");
+    }
+
+    #[test]
+    fn a_pure_newline_conflict_still_reports_a_diff() {
+        let filename = FileName::Real("src/lib.rs".into());
+        let mut emitter = StdoutEmitter::new(EmitterConfig::default());
+        let mut out = Vec::new();
+        let result = emitter
+            .emit_formatted_file(
+                &mut out,
+                FormattedFile {
+                    filename: &filename,
+                    original_text: "a\n",
+                    formatted_text: "a\n",
+                    newline_conflict: true,
+                },
+            )
+            .unwrap();
+        assert!(result.has_diff);
+    }
+}